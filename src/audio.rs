@@ -0,0 +1,79 @@
+// This plays synthesized tones rather than loading recorded keystroke
+// samples, since the repo ships no bundled audio assets — close enough to
+// a typewriter click for a no-assets build, but worth revisiting if real
+// samples ever land in the tree.
+use rodio::source::{SineWave, Source};
+use rodio::{OutputStream, OutputStreamHandle, Sink};
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A typewriter-style sound effect.
+#[derive(Debug, Clone, Copy)]
+pub enum Sound {
+    Key,
+    Return,
+}
+
+/// Plays short keystroke/typewriter sounds on a dedicated thread, so
+/// playback never blocks the `iced` update loop.
+pub struct Player {
+    sender: mpsc::Sender<Sound>,
+}
+
+impl Player {
+    pub fn spawn(volume: f32) -> Self {
+        let (sender, receiver) = mpsc::channel::<Sound>();
+
+        thread::spawn(move || {
+            let Ok((_stream, handle)) = OutputStream::try_default() else {
+                return;
+            };
+
+            while let Ok(sound) = receiver.recv() {
+                play(&handle, sound, volume);
+            }
+        });
+
+        Self { sender }
+    }
+
+    pub fn play(&self, sound: Sound) {
+        let _ = self.sender.send(sound);
+    }
+}
+
+fn play(handle: &OutputStreamHandle, sound: Sound, volume: f32) {
+    let Ok(sink) = Sink::try_new(handle) else {
+        return;
+    };
+
+    let (frequency, duration) = match sound {
+        Sound::Key => (1200.0, Duration::from_millis(18)),
+        Sound::Return => (600.0, Duration::from_millis(120)),
+    };
+
+    // Slightly detune each hit so a run of keystrokes doesn't sound
+    // perfectly identical.
+    let variant = 1.0 + (next_variant() as f32 - 2.0) * 0.02;
+
+    sink.set_volume(volume.clamp(0.0, 1.0));
+    sink.append(
+        SineWave::new(frequency * variant)
+            .take_duration(duration)
+            .amplify(0.2),
+    );
+    sink.detach();
+}
+
+/// A cheap source of variety for detuning hits: the low bits of the
+/// current time, since pulling in a full RNG crate isn't worth it for a
+/// handful of near-identical key sounds.
+fn next_variant() -> u8 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or(0) as u8
+        % 5
+}
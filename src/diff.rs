@@ -0,0 +1,186 @@
+//! A line-based unified diff, used to review a session's edits.
+
+/// Lines of context kept around each change when grouping hunks.
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone)]
+pub enum Change {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+pub struct Hunk {
+    pub header: String,
+    pub lines: Vec<Change>,
+}
+
+/// Diffs `original` against `current`, line by line, producing `@@`-style
+/// hunks with a few lines of surrounding context.
+pub fn diff(original: &str, current: &str) -> Vec<Hunk> {
+    let a: Vec<&str> = original.lines().collect();
+    let b: Vec<&str> = current.lines().collect();
+
+    let annotated = annotate(&changes(&a, &b));
+
+    hunks(&annotated)
+}
+
+/// A longest-common-subsequence line diff between `a` and `b`.
+fn changes(a: &[&str], b: &[&str]) -> Vec<Change> {
+    let mut table = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i][j] = if a[i] == b[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            changes.push(Change::Same(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            changes.push(Change::Removed(a[i].to_string()));
+            i += 1;
+        } else {
+            changes.push(Change::Added(b[j].to_string()));
+            j += 1;
+        }
+    }
+
+    changes.extend(a[i..].iter().map(|line| Change::Removed(line.to_string())));
+    changes.extend(b[j..].iter().map(|line| Change::Added(line.to_string())));
+
+    changes
+}
+
+/// Pairs each change with the (old line, new line) it falls on.
+fn annotate(changes: &[Change]) -> Vec<(Change, usize, usize)> {
+    let mut annotated = Vec::with_capacity(changes.len());
+    let (mut old_line, mut new_line) = (1, 1);
+
+    for change in changes {
+        annotated.push((change.clone(), old_line, new_line));
+
+        match change {
+            Change::Same(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            Change::Removed(_) => old_line += 1,
+            Change::Added(_) => new_line += 1,
+        }
+    }
+
+    annotated
+}
+
+fn hunks(annotated: &[(Change, usize, usize)]) -> Vec<Hunk> {
+    let mut hunks = Vec::new();
+    let mut index = 0;
+
+    while index < annotated.len() {
+        if matches!(annotated[index].0, Change::Same(_)) {
+            index += 1;
+            continue;
+        }
+
+        let start = index.saturating_sub(CONTEXT);
+        let mut end = index;
+
+        loop {
+            end += 1;
+
+            let Some((change, ..)) = annotated.get(end) else {
+                break;
+            };
+
+            if !matches!(change, Change::Same(_)) {
+                continue;
+            }
+
+            let run_start = end;
+
+            while annotated
+                .get(end)
+                .is_some_and(|(change, ..)| matches!(change, Change::Same(_)))
+            {
+                end += 1;
+            }
+
+            if end < annotated.len() && end - run_start <= CONTEXT * 2 {
+                // A small same-run bridges into more changes; keep going.
+                continue;
+            }
+
+            end = (run_start + CONTEXT.min(end - run_start)).min(annotated.len());
+            break;
+        }
+
+        let (_, old_start, new_start) = annotated[start];
+        let old_count = annotated[start..end]
+            .iter()
+            .filter(|(change, ..)| !matches!(change, Change::Added(_)))
+            .count();
+        let new_count = annotated[start..end]
+            .iter()
+            .filter(|(change, ..)| !matches!(change, Change::Removed(_)))
+            .count();
+
+        hunks.push(Hunk {
+            header: format!("@@ -{old_start},{old_count} +{new_start},{new_count} @@"),
+            lines: annotated[start..end]
+                .iter()
+                .map(|(change, ..)| change.clone())
+                .collect(),
+        });
+
+        index = end;
+    }
+
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bridges_changes_separated_by_a_small_same_run() {
+        let original = "same1\nsame2\nsame3\nOLD1\nbridge1\nbridge2\nbridge3\nOLD2\n\
+                         same4\nsame5\nsame6";
+        let current = "same1\nsame2\nsame3\nNEW1\nbridge1\nbridge2\nbridge3\nNEW2\n\
+                        same4\nsame5\nsame6";
+
+        // The same-run between the two changes is within `CONTEXT * 2`, so
+        // both changes should land in a single hunk rather than two.
+        let hunks = diff(original, current);
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].lines.len(), 13);
+    }
+
+    #[test]
+    fn trims_trailing_context_to_a_few_lines() {
+        let original = "before1\nbefore2\nbefore3\nOLD\n\
+                         after1\nafter2\nafter3\nafter4\nafter5";
+        let current = "before1\nbefore2\nbefore3\nNEW\n\
+                        after1\nafter2\nafter3\nafter4\nafter5";
+
+        let hunks = diff(original, current);
+
+        assert_eq!(hunks.len(), 1);
+        // 3 lines of leading context + the changed pair + 3 trimmed lines
+        // of trailing context, not all 5 trailing same lines.
+        assert_eq!(hunks[0].lines.len(), 8);
+    }
+}
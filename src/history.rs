@@ -0,0 +1,84 @@
+use std::time::{Duration, Instant};
+
+/// Idle gap after which a new snapshot starts its own undo unit instead of
+/// coalescing into the previous one.
+const COALESCE_WINDOW: Duration = Duration::from_millis(700);
+
+/// Upper bound on the number of snapshots kept, counting the session's
+/// original content, which is always retained as the diff anchor.
+const MAX_ENTRIES: usize = 200;
+
+/// A bounded undo/redo stack of content snapshots.
+///
+/// Snapshots recorded in quick succession (faster than [`COALESCE_WINDOW`])
+/// are merged into the most recent undo unit, so a burst of keystrokes
+/// undoes as one edit rather than one per character.
+pub struct History {
+    entries: Vec<String>,
+    redo: Vec<String>,
+    last_edit: Option<Instant>,
+}
+
+impl History {
+    pub fn new(content: String) -> Self {
+        Self {
+            entries: vec![content],
+            redo: Vec::new(),
+            last_edit: None,
+        }
+    }
+
+    /// Records `content` as the new current state.
+    pub fn record(&mut self, content: String) {
+        let now = Instant::now();
+
+        let coalesce = self
+            .last_edit
+            .is_some_and(|instant| now.duration_since(instant) < COALESCE_WINDOW);
+
+        if coalesce {
+            if let Some(current) = self.entries.last_mut() {
+                *current = content;
+            }
+        } else {
+            self.entries.push(content);
+
+            // Keep the original (index 0) as a fixed diffing anchor and
+            // drop the oldest intermediate snapshot instead.
+            if self.entries.len() > MAX_ENTRIES {
+                self.entries.remove(1);
+            }
+        }
+
+        self.redo.clear();
+        self.last_edit = Some(now);
+    }
+
+    /// Moves back one undo unit, returning the restored content.
+    pub fn undo(&mut self) -> Option<&str> {
+        if self.entries.len() < 2 {
+            return None;
+        }
+
+        let current = self.entries.pop()?;
+        self.redo.push(current);
+        self.last_edit = None;
+
+        self.entries.last().map(String::as_str)
+    }
+
+    /// Moves forward one previously undone unit, returning the restored
+    /// content.
+    pub fn redo(&mut self) -> Option<&str> {
+        let content = self.redo.pop()?;
+        self.entries.push(content);
+        self.last_edit = None;
+
+        self.entries.last().map(String::as_str)
+    }
+
+    /// The content the session started with.
+    pub fn original(&self) -> &str {
+        &self.entries[0]
+    }
+}
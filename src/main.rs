@@ -1,29 +1,138 @@
 pub mod widget;
+mod audio;
+mod diff;
+mod history;
+mod theme;
 
-use crate::widget::fade;
+use crate::audio::Player;
+use crate::history::History;
+use crate::theme::Appearance;
+use crate::widget::{backdrop, fade};
 
 use iced::event::{self, Event};
 use iced::font::{self, Font};
 use iced::keyboard;
-use iced::widget::{column, container, row, text};
+use iced::widget::{column, container, image, progress_bar, row, scrollable, text};
 use iced::window;
-use iced::{executor, Length};
+use iced::{executor, Color, Length};
 use iced::{Application, Command, Element, Settings, Subscription, Theme};
 
+use chrono::Local;
 use std::env;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Default pause between keystrokes after which a new line is considered
+/// the start of a fresh writing session, and gets stamped with a timestamp
+/// marker. Overridable with `--inactivity-threshold <seconds>`.
+const INACTIVITY_THRESHOLD: Duration = Duration::from_secs(90);
+
+/// Characters that auto-close when typed, paired with their closer.
+const PAIRS: [(char, char); 6] = [
+    ('(', ')'),
+    ('{', '}'),
+    ('[', ']'),
+    ('"', '"'),
+    ('\'', '\''),
+    ('“', '”'),
+];
+
+fn opening_pair(character: char) -> Option<char> {
+    PAIRS
+        .iter()
+        .find(|(open, _)| *open == character)
+        .map(|(_, close)| *close)
+}
+
+fn is_closing(character: char) -> bool {
+    PAIRS.iter().any(|(_, close)| *close == character)
+}
+
+/// Whether `before` looks like the start of a quoted span, rather than the
+/// middle of a word — so a quote/apostrophe only auto-closes at word
+/// boundaries instead of also firing inside contractions like `don't`.
+fn opens_quote(before: Option<char>) -> bool {
+    match before {
+        None => true,
+        Some(character) => character.is_whitespace() || opening_pair(character).is_some(),
+    }
+}
+
+fn word_count(content: &str) -> u64 {
+    content.unicode_words().count() as u64
+}
+
+/// The largest char boundary in `content` at or before `index`, so a byte
+/// offset computed from a raw length (rather than a known char boundary)
+/// can still be used to slice the string safely. UTF-8 characters are at
+/// most 4 bytes, so a boundary is always found within that many steps back.
+fn floor_char_boundary(content: &str, index: usize) -> usize {
+    (index.saturating_sub(3)..=index)
+        .rev()
+        .find(|&index| content.is_char_boundary(index))
+        .unwrap_or(0)
+}
+
+/// A timed writing session toward an optional word-count goal.
+struct Sprint {
+    started_at: Instant,
+    duration: Duration,
+    word_target: Option<u64>,
+    start_words: u64,
+    completed: bool,
+}
+
+/// Parses a `--sprint <minutes>[:<words>]` flag, e.g. `25` or `25:500`.
+fn parse_sprint(value: &str) -> Option<(Duration, Option<u64>)> {
+    let mut parts = value.splitn(2, ':');
+    let minutes: u64 = parts.next()?.parse().ok()?;
+    let word_target = parts.next().and_then(|words| words.parse().ok());
+
+    Some((Duration::from_secs(minutes * 60), word_target))
+}
 
 fn main() -> iced::Result {
-    let Some(filepath) = env::args().skip(1).next() else {
+    let mut args = env::args().skip(1);
+
+    let Some(filepath) = args.next() else {
         println!("error: no filepath specified");
-        println!("usage: kanso <filepath>");
+        println!(
+            "usage: kanso <filepath> [--backdrop <image>] [--sprint <minutes>[:<words>]] \
+             [--audio <volume>] [--inactivity-threshold <seconds>] \
+             [--theme <auto|system|dark|light|sepia>]"
+        );
 
         std::process::exit(1);
     };
 
+    let mut backdrop = None;
+    let mut sprint = None;
+    let mut audio = None;
+    let mut inactivity_threshold = INACTIVITY_THRESHOLD;
+    let mut appearance = Appearance::default();
+
+    while let Some(arg) = args.next() {
+        if arg == "--backdrop" {
+            backdrop = args.next().map(PathBuf::from);
+        } else if arg == "--sprint" {
+            sprint = args.next().and_then(|value| parse_sprint(&value));
+        } else if arg == "--audio" {
+            audio = args.next().and_then(|value| value.parse().ok());
+        } else if arg == "--inactivity-threshold" {
+            if let Some(seconds) = args.next().and_then(|value| value.parse().ok()) {
+                inactivity_threshold = Duration::from_secs(seconds);
+            }
+        } else if arg == "--theme" {
+            if let Some(value) = args.next().and_then(|value| theme::parse(&value)) {
+                appearance = value;
+            }
+        }
+    }
+
     Kanso::run(Settings {
         default_font: Font::MONOSPACE,
         window: window::Settings {
@@ -32,16 +141,36 @@ fn main() -> iced::Result {
         },
         ..Settings::with_flags(Flags {
             filepath: PathBuf::from(filepath),
+            backdrop,
+            sprint,
+            audio,
+            inactivity_threshold,
+            appearance,
         })
     })
 }
 
 enum Kanso {
-    Loading,
+    Loading {
+        backdrop: Option<PathBuf>,
+        sprint: Option<(Duration, Option<u64>)>,
+        audio: Option<f32>,
+        inactivity_threshold: Duration,
+        appearance: Appearance,
+    },
     Editing {
         filepath: PathBuf,
         content: String,
+        cursor: usize,
         is_dirty: bool,
+        last_keystroke: Option<Instant>,
+        inactivity_threshold: Duration,
+        appearance: Appearance,
+        backdrop: Option<image::Handle>,
+        sprint: Option<Sprint>,
+        history: History,
+        reviewing: bool,
+        audio: Option<Player>,
     },
     Errored {
         error: Error,
@@ -50,6 +179,11 @@ enum Kanso {
 
 struct Flags {
     filepath: PathBuf,
+    backdrop: Option<PathBuf>,
+    sprint: Option<(Duration, Option<u64>)>,
+    audio: Option<f32>,
+    inactivity_threshold: Duration,
+    appearance: Appearance,
 }
 
 #[derive(Debug, Clone)]
@@ -59,6 +193,11 @@ enum Message {
     Amend,
     Save(usize),
     Saved(Result<(), Error>),
+    Tick,
+    SprintTick,
+    Undo,
+    Redo,
+    ToggleReview,
 }
 
 impl Application for Kanso {
@@ -69,7 +208,13 @@ impl Application for Kanso {
 
     fn new(flags: Self::Flags) -> (Self, Command<Self::Message>) {
         (
-            Kanso::Loading,
+            Kanso::Loading {
+                backdrop: flags.backdrop,
+                sprint: flags.sprint,
+                audio: flags.audio,
+                inactivity_threshold: flags.inactivity_threshold,
+                appearance: flags.appearance,
+            },
             Command::perform(load(flags.filepath), Message::Loaded),
         )
     }
@@ -81,10 +226,44 @@ impl Application for Kanso {
     fn update(&mut self, message: Message) -> Command<Self::Message> {
         match message {
             Message::Loaded(Ok((filepath, content))) => {
+                let (backdrop, sprint, audio, inactivity_threshold, appearance) = match self {
+                    Self::Loading {
+                        backdrop,
+                        sprint,
+                        audio,
+                        inactivity_threshold,
+                        appearance,
+                    } => (
+                        backdrop.take(),
+                        sprint.take(),
+                        audio.take(),
+                        *inactivity_threshold,
+                        *appearance,
+                    ),
+                    _ => (None, None, None, INACTIVITY_THRESHOLD, Appearance::default()),
+                };
+
+                let cursor = content.len();
+
                 *self = Self::Editing {
                     filepath,
+                    sprint: sprint.map(|(duration, word_target)| Sprint {
+                        started_at: Instant::now(),
+                        duration,
+                        word_target,
+                        start_words: word_count(&content),
+                        completed: false,
+                    }),
+                    history: History::new((*content).clone()),
+                    reviewing: false,
+                    audio: audio.map(Player::spawn),
                     content: (*content).clone(),
+                    cursor,
                     is_dirty: false,
+                    last_keystroke: None,
+                    inactivity_threshold,
+                    appearance,
+                    backdrop: backdrop.map(image::Handle::from_path),
                 };
 
                 Command::none()
@@ -96,11 +275,54 @@ impl Application for Kanso {
             }
             Message::Write(character) => {
                 if let Self::Editing {
-                    content, is_dirty, ..
+                    content,
+                    cursor,
+                    is_dirty,
+                    last_keystroke,
+                    inactivity_threshold,
+                    history,
+                    audio,
+                    ..
                 } = self
                 {
-                    content.push(character);
+                    let is_inactive = last_keystroke
+                        .is_some_and(|instant| instant.elapsed() > *inactivity_threshold);
+
+                    if character == '\n' && is_inactive {
+                        let marker = format!("\n[{}] ", Local::now().format("%H:%M"));
+                        content.insert_str(*cursor, &marker);
+                        *cursor += marker.len();
+                    } else if character == '\n' {
+                        content.insert(*cursor, '\n');
+                        *cursor += 1;
+                    } else if is_closing(character)
+                        && content[*cursor..].chars().next() == Some(character)
+                    {
+                        // The user typed the closer of a pair we already
+                        // auto-inserted; skip over it instead of duplicating.
+                        *cursor += character.len_utf8();
+                    } else if let Some(closer) = opening_pair(character).filter(|closer| {
+                        *closer != character || opens_quote(content[..*cursor].chars().next_back())
+                    }) {
+                        content.insert(*cursor, character);
+                        *cursor += character.len_utf8();
+                        content.insert(*cursor, closer);
+                    } else {
+                        content.insert(*cursor, character);
+                        *cursor += character.len_utf8();
+                    }
+
                     *is_dirty = true;
+                    *last_keystroke = Some(Instant::now());
+                    history.record(content.clone());
+
+                    if let Some(player) = audio {
+                        player.play(if character == '\n' {
+                            audio::Sound::Return
+                        } else {
+                            audio::Sound::Key
+                        });
+                    }
 
                     Command::perform(wait_a_bit(), {
                         let version = content.len();
@@ -112,11 +334,38 @@ impl Application for Kanso {
             }
             Message::Amend => {
                 if let Self::Editing {
-                    content, is_dirty, ..
+                    content,
+                    cursor,
+                    is_dirty,
+                    last_keystroke,
+                    history,
+                    ..
                 } = self
                 {
-                    content.pop();
+                    let before = content[..*cursor].chars().next_back();
+                    let after = content[*cursor..].chars().next();
+
+                    let deleted = match (before, after) {
+                        (Some(before), Some(after)) if opening_pair(before) == Some(after) => {
+                            content.replace_range(
+                                *cursor - before.len_utf8()..*cursor + after.len_utf8(),
+                                "",
+                            );
+
+                            before.len_utf8()
+                        }
+                        (Some(before), _) => {
+                            content.replace_range(*cursor - before.len_utf8()..*cursor, "");
+
+                            before.len_utf8()
+                        }
+                        (None, _) => 0,
+                    };
+
+                    *cursor -= deleted;
                     *is_dirty = true;
+                    *last_keystroke = Some(Instant::now());
+                    history.record(content.clone());
 
                     Command::perform(wait_a_bit(), {
                         let version = content.len();
@@ -126,11 +375,65 @@ impl Application for Kanso {
                     Command::none()
                 }
             }
+            Message::Undo => {
+                if let Self::Editing {
+                    content,
+                    cursor,
+                    is_dirty,
+                    history,
+                    ..
+                } = self
+                {
+                    if let Some(restored) = history.undo() {
+                        *content = restored.to_string();
+                        *cursor = content.len();
+                        *is_dirty = true;
+
+                        return Command::perform(wait_a_bit(), {
+                            let version = content.len();
+                            move |_| Message::Save(version)
+                        });
+                    }
+                }
+
+                Command::none()
+            }
+            Message::Redo => {
+                if let Self::Editing {
+                    content,
+                    cursor,
+                    is_dirty,
+                    history,
+                    ..
+                } = self
+                {
+                    if let Some(restored) = history.redo() {
+                        *content = restored.to_string();
+                        *cursor = content.len();
+                        *is_dirty = true;
+
+                        return Command::perform(wait_a_bit(), {
+                            let version = content.len();
+                            move |_| Message::Save(version)
+                        });
+                    }
+                }
+
+                Command::none()
+            }
+            Message::ToggleReview => {
+                if let Self::Editing { reviewing, .. } = self {
+                    *reviewing = !*reviewing;
+                }
+
+                Command::none()
+            }
             Message::Save(version) => match self {
                 Self::Editing {
                     filepath,
                     content,
                     is_dirty,
+                    ..
                 } if *is_dirty && content.len() == version => {
                     Command::perform(save(filepath.clone(), content.clone()), Message::Saved)
                 }
@@ -146,13 +449,31 @@ impl Application for Kanso {
             Message::Saved(Err(error)) => {
                 *self = Self::Errored { error };
 
+                Command::none()
+            }
+            Message::Tick => Command::none(),
+            Message::SprintTick => {
+                if let Self::Editing {
+                    content, sprint: Some(sprint), ..
+                } = self
+                {
+                    let words_written = word_count(content).saturating_sub(sprint.start_words);
+                    let goal_reached = sprint
+                        .word_target
+                        .is_some_and(|target| words_written >= target);
+
+                    if sprint.started_at.elapsed() >= sprint.duration || goal_reached {
+                        sprint.completed = true;
+                    }
+                }
+
                 Command::none()
             }
         }
     }
 
     fn subscription(&self) -> Subscription<Message> {
-        event::listen_with(|event, status| {
+        let events = event::listen_with(|event, status| {
             if status == event::Status::Captured {
                 return None;
             }
@@ -169,54 +490,200 @@ impl Application for Kanso {
                     key_code: keyboard::KeyCode::Backspace,
                     ..
                 }) => Some(Message::Amend),
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key_code: keyboard::KeyCode::Z,
+                    modifiers,
+                }) if modifiers.control() && modifiers.shift() => Some(Message::Redo),
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key_code: keyboard::KeyCode::Z,
+                    modifiers,
+                }) if modifiers.control() => Some(Message::Undo),
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key_code: keyboard::KeyCode::R,
+                    modifiers,
+                }) if modifiers.control() => Some(Message::ToggleReview),
                 _ => None,
             }
-        })
+        });
+
+        let appearance = iced::time::every(Duration::from_secs(60)).map(|_| Message::Tick);
+
+        let mut subscriptions = vec![events, appearance];
+
+        if let Self::Editing {
+            sprint: Some(sprint),
+            ..
+        } = self
+        {
+            if !sprint.completed {
+                subscriptions
+                    .push(iced::time::every(Duration::from_secs(1)).map(|_| Message::SprintTick));
+            }
+        }
+
+        Subscription::batch(subscriptions)
     }
 
     fn view(&self) -> Element<'_, Message> {
         match self {
-            Self::Loading => centered("Loading..."),
+            Self::Loading { .. } => centered("Loading..."),
             Self::Editing {
                 filepath,
                 content,
+                cursor,
                 is_dirty,
+                backdrop: backdrop_image,
+                sprint,
+                history,
+                reviewing,
+                ..
             } => {
+                if *reviewing {
+                    return review(history, content);
+                }
+
+                let window_start = floor_char_boundary(content, content.len().saturating_sub(1_000));
+                let mut recent = content[window_start..].to_string();
+
+                if let Some(offset) = cursor.checked_sub(window_start) {
+                    recent.insert(offset.min(recent.len()), '_');
+                }
+
+                let lines: Vec<_> = recent.split('\n').collect();
+
                 let writer = fade(
-                    container(
-                        text(format!(
-                            "{}_",
-                            &content[content.len().saturating_sub(1_000)..]
-                        ))
-                        .font(Font {
-                            family: font::Family::Serif,
-                            ..Font::DEFAULT
-                        })
-                        .size(40),
-                    )
+                    container(column(
+                        lines
+                            .into_iter()
+                            .map(|line| {
+                                let is_marker = is_timestamp_marker(line);
+
+                                let text = text(line.to_owned())
+                                    .font(Font {
+                                        family: font::Family::Serif,
+                                        ..Font::DEFAULT
+                                    })
+                                    .size(40);
+
+                                if is_marker {
+                                    text.style(Color::from_rgba(1.0, 1.0, 1.0, 0.35)).into()
+                                } else {
+                                    text.into()
+                                }
+                            })
+                            .collect(),
+                    ))
                     .width(700)
                     .padding(20),
                 );
 
-                let status_bar = row![text(format!(
+                let path = text(format!(
                     "{}{}",
                     filepath.as_path().to_str().unwrap_or(""),
                     if *is_dirty { "*" } else { "" }
-                ))]
-                .padding(20);
+                ));
+
+                let status_bar = match sprint {
+                    Some(sprint) => {
+                        let remaining = sprint.duration.saturating_sub(sprint.started_at.elapsed());
+                        let words_written = word_count(content).saturating_sub(sprint.start_words);
+
+                        let progress = match sprint.word_target {
+                            Some(target) if target > 0 => {
+                                words_written as f32 / target as f32
+                            }
+                            _ => {
+                                sprint.started_at.elapsed().as_secs_f32()
+                                    / sprint.duration.as_secs_f32()
+                            }
+                        }
+                        .min(1.0);
+
+                        row![
+                            path,
+                            text(format!(
+                                "{:02}:{:02} left · {} words{}{}",
+                                remaining.as_secs() / 60,
+                                remaining.as_secs() % 60,
+                                words_written,
+                                sprint
+                                    .word_target
+                                    .map(|target| format!("/{target}"))
+                                    .unwrap_or_default(),
+                                if sprint.completed { " · sprint done!" } else { "" },
+                            )),
+                            progress_bar(0.0..=1.0, progress).width(200),
+                        ]
+                        .spacing(20)
+                        .padding(20)
+                    }
+                    None => row![path].padding(20),
+                };
 
-                container(column![writer, status_bar])
+                let page = container(column![writer, status_bar])
                     .width(Length::Fill)
-                    .height(Length::Fill)
-                    .into()
+                    .height(Length::Fill);
+
+                match backdrop_image {
+                    Some(handle) => backdrop(handle.clone(), page),
+                    None => page.into(),
+                }
             }
             Self::Errored { error } => centered(text(error)),
         }
     }
 
     fn theme(&self) -> Theme {
-        Theme::Dark
+        match self {
+            Self::Editing { appearance, .. } => appearance.theme(),
+            Self::Loading { .. } | Self::Errored { .. } => Theme::Dark,
+        }
+    }
+}
+
+/// Renders the session's edits as a scrollable unified diff.
+fn review<'a>(history: &History, content: &str) -> Element<'a, Message> {
+    let mut hunks = column![].spacing(2).padding(20);
+
+    for hunk in diff::diff(history.original(), content) {
+        hunks = hunks.push(
+            text(hunk.header)
+                .font(Font::MONOSPACE)
+                .size(14)
+                .style(Color::from_rgb(0.6, 0.6, 0.6)),
+        );
+
+        for line in hunk.lines {
+            let (prefix, line, color) = match line {
+                diff::Change::Same(line) => (" ", line, Color::from_rgb(0.7, 0.7, 0.7)),
+                diff::Change::Added(line) => ("+", line, Color::from_rgb(0.3, 0.8, 0.3)),
+                diff::Change::Removed(line) => ("-", line, Color::from_rgb(0.9, 0.3, 0.3)),
+            };
+
+            hunks = hunks.push(
+                text(format!("{prefix} {line}"))
+                    .font(Font::MONOSPACE)
+                    .size(14)
+                    .style(color),
+            );
+        }
     }
+
+    container(scrollable(hunks))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+/// Whether `line` begins with an inactivity marker, e.g. `[14:32]`.
+fn is_timestamp_marker(line: &str) -> bool {
+    let bytes = line.as_bytes();
+
+    bytes.len() >= 7
+        && bytes[0] == b'['
+        && bytes[3] == b':'
+        && bytes[6] == b']'
+        && [1, 2, 4, 5].iter().all(|&index| bytes[index].is_ascii_digit())
 }
 
 fn centered<'a>(content: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
@@ -0,0 +1,83 @@
+use iced::theme;
+use iced::{Color, Theme};
+
+use chrono::Timelike;
+
+/// Which palette the editor should render with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Appearance {
+    /// Follows a day/night schedule: a warm, paper-like palette during the
+    /// day, and a dark palette once the evening sets in.
+    #[default]
+    Auto,
+    /// Follows the platform's light/dark preference, falling back to
+    /// [`Appearance::Auto`]'s schedule when that preference can't be read.
+    System,
+    Dark,
+    Light,
+    Sepia,
+}
+
+impl Appearance {
+    /// Resolves the concrete [`Theme`] this appearance maps to right now,
+    /// following the day/night schedule when set to [`Appearance::Auto`],
+    /// or the platform's preference when set to [`Appearance::System`].
+    pub fn theme(self) -> Theme {
+        match self {
+            Appearance::Auto => Self::scheduled(),
+            Appearance::System => Self::system(),
+            Appearance::Dark => Theme::Dark,
+            Appearance::Light => Theme::Light,
+            Appearance::Sepia => sepia(),
+        }
+    }
+
+    /// A warm, paper-like palette during the day, and a dark palette once
+    /// the evening sets in.
+    fn scheduled() -> Theme {
+        match local_hour() {
+            6..=18 => sepia(),
+            _ => Theme::Dark,
+        }
+    }
+
+    /// The platform's light/dark preference, falling back to the day/night
+    /// schedule when the preference can't be detected.
+    fn system() -> Theme {
+        match dark_light::detect() {
+            Ok(dark_light::Mode::Dark) => Theme::Dark,
+            Ok(dark_light::Mode::Light) => Theme::Light,
+            Ok(dark_light::Mode::Unspecified) | Err(_) => Self::scheduled(),
+        }
+    }
+}
+
+/// Parses a `--theme <auto|system|dark|light|sepia>` flag value.
+pub fn parse(value: &str) -> Option<Appearance> {
+    match value {
+        "auto" => Some(Appearance::Auto),
+        "system" => Some(Appearance::System),
+        "dark" => Some(Appearance::Dark),
+        "light" => Some(Appearance::Light),
+        "sepia" => Some(Appearance::Sepia),
+        _ => None,
+    }
+}
+
+fn sepia() -> Theme {
+    Theme::Custom(Box::new(theme::Custom::new(
+        String::from("Sepia"),
+        theme::Palette {
+            background: Color::from_rgb8(0xF4, 0xEC, 0xD8),
+            text: Color::from_rgb8(0x5B, 0x40, 0x32),
+            primary: Color::from_rgb8(0x9C, 0x6B, 0x4F),
+            success: Color::from_rgb8(0x4C, 0x6B, 0x3A),
+            danger: Color::from_rgb8(0xA6, 0x3B, 0x3B),
+        },
+    )))
+}
+
+/// The current hour of the day, in the local timezone (`0..=23`).
+fn local_hour() -> u32 {
+    chrono::Local::now().hour()
+}
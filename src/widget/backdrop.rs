@@ -0,0 +1,133 @@
+use iced::advanced;
+use iced::advanced::image;
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::renderer;
+use iced::advanced::widget::{self, Widget};
+use iced::mouse;
+use iced::{Background, Color, Element, Length, Rectangle, Size};
+
+/// Wraps `content` with a backdrop image filling the window behind it,
+/// dimmed slightly so text drawn on top stays legible.
+///
+/// Unlike redrawing on [`iced::window::Event::Resized`], the image is
+/// stretched to `layout.bounds()` on every `draw`, so it always matches
+/// the current window size without any extra bookkeeping.
+pub fn backdrop<'a, Message, Renderer>(
+    handle: impl Into<image::Handle>,
+    content: impl Into<Element<'a, Message, Renderer>>,
+) -> Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: advanced::Renderer + advanced::image::Renderer<Handle = image::Handle> + 'a,
+{
+    Backdrop {
+        handle: handle.into(),
+        content: content.into(),
+    }
+    .into()
+}
+
+struct Backdrop<'a, Message, Renderer> {
+    handle: image::Handle,
+    content: Element<'a, Message, Renderer>,
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for Backdrop<'a, Message, Renderer>
+where
+    Renderer: advanced::Renderer + advanced::image::Renderer<Handle = image::Handle>,
+{
+    fn tag(&self) -> widget::tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> widget::tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<widget::Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut widget::Tree) {
+        self.content.as_widget().diff(tree)
+    }
+
+    fn width(&self) -> Length {
+        Length::Fill
+    }
+
+    fn height(&self) -> Length {
+        Length::Fill
+    }
+
+    fn layout(
+        &self,
+        tree: &mut widget::Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let size = limits.max();
+
+        let content_layout =
+            self.content
+                .as_widget()
+                .layout(tree, renderer, &layout::Limits::new(Size::ZERO, size));
+
+        layout::Node::with_children(size, vec![content_layout])
+    }
+
+    fn draw(
+        &self,
+        tree: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &<Renderer as advanced::Renderer>::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+
+        renderer.with_layer(bounds, |renderer| {
+            advanced::image::Renderer::draw_image(
+                renderer,
+                self.handle.clone(),
+                image::FilterMethod::Linear,
+                bounds,
+                [0.0; 4],
+            );
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds,
+                    border_radius: 0.0.into(),
+                    border_width: 0.0,
+                    border_color: Color::TRANSPARENT,
+                },
+                Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.45)),
+            );
+        });
+
+        let content_layout = layout.children().next().unwrap();
+
+        self.content.as_widget().draw(
+            tree,
+            renderer,
+            theme,
+            style,
+            content_layout,
+            cursor,
+            viewport,
+        );
+    }
+}
+
+impl<'a, Message, Renderer> From<Backdrop<'a, Message, Renderer>> for Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: advanced::Renderer + advanced::image::Renderer<Handle = image::Handle> + 'a,
+{
+    fn from(backdrop: Backdrop<'a, Message, Renderer>) -> Self {
+        Element::new(backdrop)
+    }
+}
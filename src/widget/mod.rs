@@ -0,0 +1,5 @@
+mod backdrop;
+mod fade;
+
+pub use backdrop::backdrop;
+pub use fade::fade;